@@ -1,4 +1,7 @@
 #[macro_use] extern crate scan_fmt;
+extern crate serde;
+#[macro_use] extern crate serde_derive;
+extern crate bincode;
 use std::io::{self, Write};
 use std::io::prelude::*;
 use std::fs::OpenOptions;
@@ -11,23 +14,16 @@ use std::fs::File;
 use std::io::SeekFrom;
 use std::env;
 
-const COLUMN_USERNAME_SIZE: usize = 32;
-const COLUMN_EMAIL_SIZE: usize = 255;
-
 const ID_SIZE: usize = mem::size_of::<i32>();
-const USERNAME_SIZE: usize = mem::size_of::<[u8; COLUMN_USERNAME_SIZE]>();
-const EMAIL_SIZE: usize = mem::size_of::<[u8; COLUMN_EMAIL_SIZE]>();
-
-const ID_OFFSET: usize = 0;
-const USERNAME_OFFSET: usize = ID_OFFSET + ID_SIZE;
-const EMAIL_OFFSET: usize = USERNAME_OFFSET + USERNAME_SIZE;
 
-const ROW_SIZE: usize = ID_SIZE + USERNAME_SIZE + EMAIL_SIZE;
+// Size of a leaf cell's value slot, kept at the original fixed-width row
+// footprint. The slot ends in an overflow pointer, so a row whose encoding plus
+// its varint length prefix exceeds LEAF_NODE_LOCAL_VALUE_SIZE spills into
+// overflow pages.
+const ROW_SIZE: usize = 291;
 
 const PAGE_SIZE: usize = 4096;
 const TABLE_MAX_PAGES: usize = 100;
-const ROWS_PER_PAGE: usize = PAGE_SIZE / ROW_SIZE;
-const TABLE_MAX_ROWS: usize = ROWS_PER_PAGE * TABLE_MAX_PAGES;
 
 // Common Node Header Layout
 const NODE_TYPE_SIZE: usize = mem::size_of::<u8>();
@@ -42,18 +38,51 @@ const COMMON_NODE_HEADER_SIZE: usize =
 // Leaf Node Header Layout
 const LEAF_NODE_NUM_CELLS_SIZE: usize = mem::size_of::<u32>();
 const LEAF_NODE_NUM_CELLS_OFFSET: usize = COMMON_NODE_HEADER_SIZE;
+const LEAF_NODE_NEXT_LEAF_SIZE: usize = mem::size_of::<u32>();
+const LEAF_NODE_NEXT_LEAF_OFFSET: usize =
+    LEAF_NODE_NUM_CELLS_OFFSET + LEAF_NODE_NUM_CELLS_SIZE;
 const LEAF_NODE_HEADER_SIZE: usize =
-    COMMON_NODE_HEADER_SIZE + LEAF_NODE_NUM_CELLS_SIZE;
+    COMMON_NODE_HEADER_SIZE + LEAF_NODE_NUM_CELLS_SIZE + LEAF_NODE_NEXT_LEAF_SIZE;
 
 // Leaf Node Body Layout
 const LEAF_NODE_KEY_SIZE: usize = mem::size_of::<u32>();
-const LEAF_NODE_KEY_OFFSET: usize = 0;
 const LEAF_NODE_VALUE_SIZE: usize = ROW_SIZE;
-const LEAF_NODE_VALUE_OFFSET: usize = LEAF_NODE_KEY_OFFSET + LEAF_NODE_KEY_SIZE;
 const LEAF_NODE_CELL_SIZE: usize = LEAF_NODE_KEY_SIZE + LEAF_NODE_VALUE_SIZE;
 const LEAF_NODE_SPACE_FOR_CELLS: usize = PAGE_SIZE - LEAF_NODE_HEADER_SIZE;
 const LEAF_NODE_MAX_CELLS: usize = LEAF_NODE_SPACE_FOR_CELLS / LEAF_NODE_CELL_SIZE;
 
+const LEAF_NODE_RIGHT_SPLIT_COUNT: usize = LEAF_NODE_MAX_CELLS.div_ceil(2);
+const LEAF_NODE_LEFT_SPLIT_COUNT: usize =
+    (LEAF_NODE_MAX_CELLS + 1) - LEAF_NODE_RIGHT_SPLIT_COUNT;
+
+// The last word of a cell's value slot holds the page number of the first
+// overflow page (0 when the payload fits locally); the rest stores the head of
+// the payload.
+const OVERFLOW_POINTER_SIZE: usize = mem::size_of::<u32>();
+const LEAF_NODE_LOCAL_VALUE_SIZE: usize = LEAF_NODE_VALUE_SIZE - OVERFLOW_POINTER_SIZE;
+
+// Overflow Page Layout: a 4-byte "next overflow page" pointer followed by data.
+const OVERFLOW_PAGE_HEADER_SIZE: usize = mem::size_of::<u32>();
+const OVERFLOW_PAGE_DATA_SIZE: usize = PAGE_SIZE - OVERFLOW_PAGE_HEADER_SIZE;
+
+// Internal Node Header Layout
+const INTERNAL_NODE_NUM_KEYS_SIZE: usize = mem::size_of::<u32>();
+const INTERNAL_NODE_NUM_KEYS_OFFSET: usize = COMMON_NODE_HEADER_SIZE;
+const INTERNAL_NODE_RIGHT_CHILD_SIZE: usize = mem::size_of::<u32>();
+const INTERNAL_NODE_RIGHT_CHILD_OFFSET: usize =
+    INTERNAL_NODE_NUM_KEYS_OFFSET + INTERNAL_NODE_NUM_KEYS_SIZE;
+const INTERNAL_NODE_HEADER_SIZE: usize = COMMON_NODE_HEADER_SIZE
+    + INTERNAL_NODE_NUM_KEYS_SIZE + INTERNAL_NODE_RIGHT_CHILD_SIZE;
+
+// Internal Node Body Layout
+const INTERNAL_NODE_KEY_SIZE: usize = mem::size_of::<u32>();
+const INTERNAL_NODE_CHILD_SIZE: usize = mem::size_of::<u32>();
+const INTERNAL_NODE_CELL_SIZE: usize =
+    INTERNAL_NODE_CHILD_SIZE + INTERNAL_NODE_KEY_SIZE;
+const INTERNAL_NODE_SPACE_FOR_CELLS: usize = PAGE_SIZE - INTERNAL_NODE_HEADER_SIZE;
+const INTERNAL_NODE_MAX_CELLS: usize =
+    INTERNAL_NODE_SPACE_FOR_CELLS / INTERNAL_NODE_CELL_SIZE;
+
 #[derive(Debug)]
 enum StatementType {
     Insert,
@@ -64,7 +93,6 @@ enum StatementType {
 enum PrepareError {
     UnrecognizedStatement,
     SyntaxError,
-    StringTooLong,
     NegativeId,
 }
 
@@ -74,47 +102,117 @@ enum PagerError {
     EmptyPageFlush,
 }
 
+#[derive(Debug, PartialEq)]
 enum NodeType {
     Internal,
     Leaf,
 }
 
-#[derive(Debug)]
-#[repr(C)]
+#[derive(Debug, Serialize, Deserialize)]
 struct Row {
     id: u32,
-    username: [u8; COLUMN_USERNAME_SIZE],
-    email: [u8; COLUMN_EMAIL_SIZE],
+    username: String,
+    email: String,
 }
 
-fn str_from_array(arr: &[u8]) -> &str {
-    let null_pos = arr.iter().position(|&c| c == b'\0').unwrap_or(arr.len());
-    str::from_utf8(&arr[..null_pos]).unwrap()
+// A row codec owns the on-disk representation of a `Row`, decoupling the cell
+// format from the leaf-node machinery: `encode` writes into `buffer` and
+// returns the number of bytes written, `decode` rebuilds a row from them.
+trait RowCodec {
+    fn encode(&self, row: &Row, buffer: &mut [u8]) -> usize;
+    fn decode(&self, bytes: &[u8]) -> Row;
 }
 
-impl Row {
-    fn deserialize(bytes: &[u8]) -> Self {
-        let mut username = [0u8; COLUMN_USERNAME_SIZE];
-        let mut email = [0u8; COLUMN_EMAIL_SIZE];
-        let id = u32::from_le_bytes(
-            bytes[ID_OFFSET..ID_OFFSET+ID_SIZE].try_into().unwrap()
-        );
-        username.copy_from_slice(&bytes[USERNAME_OFFSET..USERNAME_OFFSET+USERNAME_SIZE]);
-        email.copy_from_slice(&bytes[EMAIL_OFFSET..EMAIL_OFFSET+EMAIL_SIZE]);
-        Self { id, username, email }
+// A hand-rolled codec: id as little-endian, then each string prefixed with its
+// varint byte length. This is the variable-length format introduced alongside
+// overflow pages, kept as an alternate to the bincode default.
+struct ManualCodec;
+
+impl RowCodec for ManualCodec {
+    fn encode(&self, row: &Row, buffer: &mut [u8]) -> usize {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&row.id.to_le_bytes());
+        write_varint(&mut bytes, row.username.len() as u64);
+        bytes.extend_from_slice(row.username.as_bytes());
+        write_varint(&mut bytes, row.email.len() as u64);
+        bytes.extend_from_slice(row.email.as_bytes());
+        buffer[..bytes.len()].copy_from_slice(&bytes);
+        bytes.len()
+    }
+
+    fn decode(&self, bytes: &[u8]) -> Row {
+        let id = u32::from_le_bytes(bytes[..ID_SIZE].try_into().unwrap());
+        let mut offset = ID_SIZE;
+
+        let (username_len, consumed) = parse_varint(&bytes[offset..]);
+        offset += consumed;
+        let username = str::from_utf8(
+            &bytes[offset..offset + username_len as usize]
+        ).unwrap().to_string();
+        offset += username_len as usize;
+
+        let (email_len, consumed) = parse_varint(&bytes[offset..]);
+        offset += consumed;
+        let email = str::from_utf8(
+            &bytes[offset..offset + email_len as usize]
+        ).unwrap().to_string();
+
+        Row { id, username, email }
+    }
+}
+
+// The default codec. bincode's fixed-int encoding keeps the layout stable, and
+// deriving `Serialize`/`Deserialize` means a new column only needs a new field.
+struct BincodeCodec;
+
+impl RowCodec for BincodeCodec {
+    fn encode(&self, row: &Row, buffer: &mut [u8]) -> usize {
+        let bytes = bincode::serialize(row).unwrap();
+        buffer[..bytes.len()].copy_from_slice(&bytes);
+        bytes.len()
+    }
+
+    fn decode(&self, bytes: &[u8]) -> Row {
+        bincode::deserialize(bytes).unwrap()
     }
+}
 
-    fn serialize(&self, buffer: &mut [u8]) {
-        buffer[ID_OFFSET..ID_OFFSET+ID_SIZE].copy_from_slice(&self.id.to_le_bytes());
-        buffer[USERNAME_OFFSET..USERNAME_OFFSET+USERNAME_SIZE]
-            .copy_from_slice(&self.username);
-        buffer[EMAIL_OFFSET..EMAIL_OFFSET+EMAIL_SIZE].copy_from_slice(&self.email);
+// LEB128-style varint: seven payload bits per byte, high bit set on every byte
+// but the last. Used to length-prefix the variable-length string columns.
+fn write_varint(buffer: &mut Vec<u8>, mut value: u64) {
+    loop {
+        let mut byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value != 0 {
+            byte |= 0x80;
+        }
+        buffer.push(byte);
+        if value == 0 {
+            break;
+        }
     }
+}
 
+// Decode a varint from the front of `bytes`, returning the value and the number
+// of bytes consumed.
+fn parse_varint(bytes: &[u8]) -> (u64, usize) {
+    let mut result = 0u64;
+    let mut shift = 0;
+    let mut consumed = 0;
+    for &byte in bytes {
+        result |= ((byte & 0x7f) as u64) << shift;
+        consumed += 1;
+        if byte & 0x80 == 0 {
+            break;
+        }
+        shift += 7;
+    }
+    (result, consumed)
+}
+
+impl Row {
     fn print(&self) {
-        let username_str = str_from_array(&self.username);
-        let email_str = str_from_array(&self.email);
-        println!("({}, {}, {})", self.id, username_str, email_str);
+        println!("({}, {}, {})", self.id, self.username, self.email);
     }
 }
 
@@ -135,32 +233,18 @@ impl Statement {
                 if id < 0 {
                     return Err(PrepareError::NegativeId)
                 }
-                if username.len() > COLUMN_USERNAME_SIZE {
-                    return Err(PrepareError::StringTooLong)
-                }
-                if email.len() > COLUMN_EMAIL_SIZE {
-                    return Err(PrepareError::StringTooLong)
-                }
 
-                let mut username_bytes = [0u8; COLUMN_USERNAME_SIZE];
-                let mut email_bytes = [0u8; COLUMN_EMAIL_SIZE];
-
-                username_bytes[..cmp::min(username.len(), COLUMN_USERNAME_SIZE)]
-                    .copy_from_slice(username.as_bytes());
-                email_bytes[..cmp::min(email.len(), COLUMN_EMAIL_SIZE)]
-                    .copy_from_slice(email.as_bytes());
-
-                return Ok(Self {
+                Ok(Self {
                     statement_type: StatementType::Insert,
                     row_to_insert: Some(Row {
                         id: id as u32,
-                        username: username_bytes,
-                        email: email_bytes,
+                        username,
+                        email,
                     })
-                });
+                })
             },
-            Err(_) => return Err(PrepareError::SyntaxError)
-        };
+            Err(_) => Err(PrepareError::SyntaxError)
+        }
     }
 
     fn prepare(statement_text: &str) -> Result<Self, PrepareError> {
@@ -194,11 +278,12 @@ impl Pager {
             .write(true)
             .read(true)
             .create(true)
+            .truncate(false)
             .open(filename)
             .unwrap();
         let file_length = file.seek(SeekFrom::End(0)).unwrap();
 
-        if file_length as usize % PAGE_SIZE != 0 {
+        if !(file_length as usize).is_multiple_of(PAGE_SIZE) {
             panic!("Db file is not a whole number of pages. Corrupt file.");
         }
 
@@ -216,17 +301,17 @@ impl Pager {
             return Err(PagerError::PageNumberOutOfBounds);
         }
 
-        if let None = self.pages[page_num] {
+        if self.pages[page_num].is_none() {
             let mut page = Box::new([0u8; PAGE_SIZE]);
             let mut num_pages = self.file_length as usize / PAGE_SIZE;
 
-            if self.file_length % PAGE_SIZE as u64 != 0 {
+            if !self.file_length.is_multiple_of(PAGE_SIZE as u64) {
                 num_pages += 1;
             }
 
-            if page_num <= num_pages {
+            if page_num < num_pages {
                 self.file.seek(SeekFrom::Start((page_num * PAGE_SIZE) as u64)).unwrap();
-                self.file.read(&mut *page).unwrap();
+                self.file.read_exact(&mut *page).unwrap();
             }
 
             self.pages[page_num].replace(page);
@@ -240,36 +325,53 @@ impl Pager {
     }
 
     fn flush(&mut self, page_num: usize) -> Result<(), PagerError> {
-        if let None = self.pages[page_num] {
+        if self.pages[page_num].is_none() {
             return Err(PagerError::EmptyPageFlush);
         }
 
         self.file.seek(SeekFrom::Start((page_num * PAGE_SIZE) as u64)).unwrap();
-        self.file.write(
+        self.file.write_all(
             self.pages[page_num].as_ref().unwrap().as_ref()
         ).unwrap();
 
         Ok(())
     }
+
+    // Reserve a fresh page for overflow storage and return its page number.
+    fn allocate_overflow_page(&mut self) -> u32 {
+        let page_num = self.num_pages;
+        self.get_page(page_num as usize).unwrap();
+        page_num
+    }
 }
 
-#[derive(Debug)]
-#[repr(C)]
 struct Table {
     root_page_num: u32,
     pager: Pager,
+    codec: Box<dyn RowCodec>,
 }
 
 impl Table {
     fn new(filename: &str) -> Self {
+        // The row codec defaults to bincode but can be switched to the manual
+        // little-endian encoder with `DB_CODEC=manual`.
+        let codec: Box<dyn RowCodec> = match env::var("DB_CODEC").as_deref() {
+            Ok("manual") => Box::new(ManualCodec),
+            _ => Box::new(BincodeCodec),
+        };
+        Self::with_codec(filename, codec)
+    }
+
+    fn with_codec(filename: &str, codec: Box<dyn RowCodec>) -> Self {
         let mut pager = Pager::open(filename);
 
         if pager.num_pages == 0 {
             let root_node = pager.get_page(0).unwrap();
             initialize_leaf_node(root_node);
+            set_node_root(root_node, true);
         }
 
-        Self { root_page_num: 0, pager }
+        Self { root_page_num: 0, pager, codec }
     }
 
     fn close(&mut self) {
@@ -294,6 +396,18 @@ fn leaf_node_set_num_cells(node: &mut [u8], num_cells: u32) {
     node[start..end].copy_from_slice(&num_cells.to_le_bytes());
 }
 
+fn leaf_node_next_leaf(node: &[u8]) -> u32 {
+    let start = LEAF_NODE_NEXT_LEAF_OFFSET;
+    let end = start + LEAF_NODE_NEXT_LEAF_SIZE;
+    u32::from_le_bytes(node[start..end].try_into().unwrap())
+}
+
+fn leaf_node_set_next_leaf(node: &mut [u8], next_leaf: u32) {
+    let start = LEAF_NODE_NEXT_LEAF_OFFSET;
+    let end = start + LEAF_NODE_NEXT_LEAF_SIZE;
+    node[start..end].copy_from_slice(&next_leaf.to_le_bytes());
+}
+
 fn leaf_node_cell(node: &mut [u8], cell_num: u32) -> &mut [u8] {
     let start = LEAF_NODE_HEADER_SIZE + cell_num as usize * LEAF_NODE_CELL_SIZE;
     let end = start + LEAF_NODE_CELL_SIZE;
@@ -332,25 +446,450 @@ fn leaf_node_value(node: &mut [u8], cell_num: u32) -> &mut [u8] {
     &mut cell[LEAF_NODE_KEY_SIZE..]
 }
 
-fn initialize_leaf_node(_node: &mut [u8]) {}
+fn get_node_type(node: &[u8]) -> NodeType {
+    match node[NODE_TYPE_OFFSET] {
+        0 => NodeType::Internal,
+        _ => NodeType::Leaf,
+    }
+}
+
+fn set_node_type(node: &mut [u8], node_type: NodeType) {
+    node[NODE_TYPE_OFFSET] = match node_type {
+        NodeType::Internal => 0,
+        NodeType::Leaf => 1,
+    };
+}
+
+fn is_node_root(node: &[u8]) -> bool {
+    node[IS_ROOT_OFFSET] != 0
+}
+
+fn set_node_root(node: &mut [u8], is_root: bool) {
+    node[IS_ROOT_OFFSET] = is_root as u8;
+}
+
+fn node_parent(node: &[u8]) -> u32 {
+    let start = PARENT_POINTER_OFFSET;
+    let end = start + PARENT_POINTER_SIZE;
+    u32::from_le_bytes(node[start..end].try_into().unwrap())
+}
+
+fn set_node_parent(node: &mut [u8], parent: u32) {
+    let start = PARENT_POINTER_OFFSET;
+    let end = start + PARENT_POINTER_SIZE;
+    node[start..end].copy_from_slice(&parent.to_le_bytes());
+}
+
+fn internal_node_num_keys(node: &[u8]) -> u32 {
+    let start = INTERNAL_NODE_NUM_KEYS_OFFSET;
+    let end = start + INTERNAL_NODE_NUM_KEYS_SIZE;
+    u32::from_le_bytes(node[start..end].try_into().unwrap())
+}
+
+fn set_internal_node_num_keys(node: &mut [u8], num_keys: u32) {
+    let start = INTERNAL_NODE_NUM_KEYS_OFFSET;
+    let end = start + INTERNAL_NODE_NUM_KEYS_SIZE;
+    node[start..end].copy_from_slice(&num_keys.to_le_bytes());
+}
+
+fn internal_node_right_child(node: &[u8]) -> u32 {
+    let start = INTERNAL_NODE_RIGHT_CHILD_OFFSET;
+    let end = start + INTERNAL_NODE_RIGHT_CHILD_SIZE;
+    u32::from_le_bytes(node[start..end].try_into().unwrap())
+}
+
+fn set_internal_node_right_child(node: &mut [u8], child: u32) {
+    let start = INTERNAL_NODE_RIGHT_CHILD_OFFSET;
+    let end = start + INTERNAL_NODE_RIGHT_CHILD_SIZE;
+    node[start..end].copy_from_slice(&child.to_le_bytes());
+}
+
+fn internal_node_cell(node: &mut [u8], cell_num: u32) -> &mut [u8] {
+    let start = INTERNAL_NODE_HEADER_SIZE + cell_num as usize * INTERNAL_NODE_CELL_SIZE;
+    let end = start + INTERNAL_NODE_CELL_SIZE;
+    &mut node[start..end]
+}
+
+fn internal_node_child(node: &mut [u8], child_num: u32) -> u32 {
+    let cell = internal_node_cell(node, child_num);
+    u32::from_le_bytes(cell[..INTERNAL_NODE_CHILD_SIZE].try_into().unwrap())
+}
+
+fn set_internal_node_child(node: &mut [u8], child_num: u32, child: u32) {
+    let cell = internal_node_cell(node, child_num);
+    cell[..INTERNAL_NODE_CHILD_SIZE].copy_from_slice(&child.to_le_bytes());
+}
+
+fn internal_node_key(node: &mut [u8], key_num: u32) -> u32 {
+    let cell = internal_node_cell(node, key_num);
+    u32::from_le_bytes(cell[INTERNAL_NODE_CHILD_SIZE..].try_into().unwrap())
+}
+
+fn set_internal_node_key(node: &mut [u8], key_num: u32, key: u32) {
+    let cell = internal_node_cell(node, key_num);
+    cell[INTERNAL_NODE_CHILD_SIZE..].copy_from_slice(&key.to_le_bytes());
+}
+
+// After a child's max key changes, rewrite the separator key that points at it.
+fn update_internal_node_key(node: &mut [u8], old_key: u32, new_key: u32) {
+    let old_index = internal_node_find_child(node, old_key);
+    set_internal_node_key(node, old_index, new_key);
+}
+
+fn get_node_max_key(node: &mut [u8]) -> u32 {
+    match get_node_type(node) {
+        NodeType::Internal => {
+            let num_keys = internal_node_num_keys(node);
+            internal_node_key(node, num_keys - 1)
+        },
+        NodeType::Leaf => {
+            let num_cells = leaf_node_num_cells(node);
+            leaf_node_key(node, num_cells - 1)
+        },
+    }
+}
+
+fn initialize_leaf_node(node: &mut [u8]) {
+    set_node_type(node, NodeType::Leaf);
+    set_node_root(node, false);
+    set_node_parent(node, 0);
+    leaf_node_set_num_cells(node, 0);
+    leaf_node_set_next_leaf(node, 0);
+}
+
+fn initialize_internal_node(node: &mut [u8]) {
+    set_node_type(node, NodeType::Internal);
+    set_node_root(node, false);
+    set_node_parent(node, 0);
+    set_internal_node_num_keys(node, 0);
+}
+
+// Splitting the root replaces the old root with a new internal node whose two
+// children are the left half (a copy of the old root) and the freshly created
+// right half. Handling the root specially keeps `root_page_num` stable.
+fn create_new_root(table: &mut Table, right_child_page_num: u32) {
+    let root_page_num = table.root_page_num as usize;
+    let left_child_page_num = table.pager.num_pages as usize;
+
+    let root_copy = table.pager.get_page(root_page_num).unwrap().to_vec();
+    let left_child = table.pager.get_page(left_child_page_num).unwrap();
+    left_child.copy_from_slice(&root_copy);
+    set_node_root(left_child, false);
+    let left_max_key = get_node_max_key(left_child);
+
+    let root = table.pager.get_page(root_page_num).unwrap();
+    initialize_internal_node(root);
+    set_node_root(root, true);
+    set_internal_node_num_keys(root, 1);
+    set_internal_node_child(root, 0, left_child_page_num as u32);
+    set_internal_node_key(root, 0, left_max_key);
+    set_internal_node_right_child(root, right_child_page_num);
+
+    let root_page_num = root_page_num as u32;
+    set_node_parent(
+        table.pager.get_page(left_child_page_num).unwrap(), root_page_num
+    );
+    set_node_parent(
+        table.pager.get_page(right_child_page_num as usize).unwrap(), root_page_num
+    );
+}
+
+// Distribute the full leaf's cells plus the new one across the old (left) leaf
+// and a freshly allocated right leaf, then either grow a new root or update the
+// parent.
+fn leaf_node_split_and_insert(cursor: &mut Cursor, key: u32, value_slot: &[u8]) {
+    let old_page_num = cursor.page_num;
+    let new_page_num = cursor.table.pager.num_pages as usize;
+
+    let (old_max, old_next_leaf) = {
+        let old_node = cursor.table.pager.get_page(old_page_num).unwrap();
+        (get_node_max_key(old_node), leaf_node_next_leaf(old_node))
+    };
+
+    let new_node = cursor.table.pager.get_page(new_page_num).unwrap();
+    initialize_leaf_node(new_node);
+    leaf_node_set_next_leaf(new_node, old_next_leaf);
+
+    let old_copy = cursor.table.pager.get_page(old_page_num).unwrap().to_vec();
+
+    for i in (0..=LEAF_NODE_MAX_CELLS).rev() {
+        let destination_page_num = if i >= LEAF_NODE_LEFT_SPLIT_COUNT {
+            new_page_num
+        } else {
+            old_page_num
+        };
+        let index_within_node = (i % LEAF_NODE_LEFT_SPLIT_COUNT) as u32;
+        let destination_node = cursor.table.pager.get_page(destination_page_num).unwrap();
+
+        if i == cursor.cell_num {
+            leaf_node_set_key(destination_node, index_within_node, key);
+            leaf_node_value(destination_node, index_within_node)
+                .copy_from_slice(value_slot);
+        } else {
+            let src_index = if i > cursor.cell_num { i - 1 } else { i };
+            let src_start = LEAF_NODE_HEADER_SIZE + src_index * LEAF_NODE_CELL_SIZE;
+            let cell = leaf_node_cell(destination_node, index_within_node);
+            cell.copy_from_slice(&old_copy[src_start..src_start + LEAF_NODE_CELL_SIZE]);
+        }
+    }
+
+    let old_node = cursor.table.pager.get_page(old_page_num).unwrap();
+    leaf_node_set_num_cells(old_node, LEAF_NODE_LEFT_SPLIT_COUNT as u32);
+    leaf_node_set_next_leaf(old_node, new_page_num as u32);
+    let new_node = cursor.table.pager.get_page(new_page_num).unwrap();
+    leaf_node_set_num_cells(new_node, LEAF_NODE_RIGHT_SPLIT_COUNT as u32);
+
+    let old_node = cursor.table.pager.get_page(old_page_num).unwrap();
+    if is_node_root(old_node) {
+        create_new_root(cursor.table, new_page_num as u32);
+    } else {
+        let parent_page_num = node_parent(old_node);
+        let new_max = {
+            let old_node = cursor.table.pager.get_page(old_page_num).unwrap();
+            get_node_max_key(old_node)
+        };
+        {
+            let parent = cursor.table.pager.get_page(parent_page_num as usize).unwrap();
+            update_internal_node_key(parent, old_max, new_max);
+        }
+        {
+            let new_node = cursor.table.pager.get_page(new_page_num).unwrap();
+            set_node_parent(new_node, parent_page_num);
+        }
+        internal_node_insert(cursor.table, parent_page_num, new_page_num as u32);
+    }
+}
+
+// Add a child/key pair to an internal node after one of its children split.
+// Internal-node splitting is not implemented, which caps the tree at a single
+// internal level (~INTERNAL_NODE_MAX_CELLS * LEAF_NODE_MAX_CELLS rows) — far
+// beyond anything the tests here exercise.
+fn internal_node_insert(table: &mut Table, parent_page_num: u32, child_page_num: u32) {
+    let child_max_key = {
+        let child = table.pager.get_page(child_page_num as usize).unwrap();
+        get_node_max_key(child)
+    };
+
+    let (index, original_num_keys) = {
+        let parent = table.pager.get_page(parent_page_num as usize).unwrap();
+        (internal_node_find_child(parent, child_max_key), internal_node_num_keys(parent))
+    };
+
+    if original_num_keys as usize >= INTERNAL_NODE_MAX_CELLS {
+        panic!("Need to implement splitting internal node.");
+    }
+
+    let right_child_page_num = {
+        let parent = table.pager.get_page(parent_page_num as usize).unwrap();
+        internal_node_right_child(parent)
+    };
+    let right_child_max_key = {
+        let right_child = table.pager.get_page(right_child_page_num as usize).unwrap();
+        get_node_max_key(right_child)
+    };
+
+    let parent = table.pager.get_page(parent_page_num as usize).unwrap();
+    set_internal_node_num_keys(parent, original_num_keys + 1);
+
+    if child_max_key > right_child_max_key {
+        // The new child becomes the rightmost; the old right child moves down
+        // into the last key cell.
+        set_internal_node_child(parent, original_num_keys, right_child_page_num);
+        set_internal_node_key(parent, original_num_keys, right_child_max_key);
+        set_internal_node_right_child(parent, child_page_num);
+    } else {
+        // Shift cells from the insertion point right to open a slot.
+        for i in (index + 1..=original_num_keys).rev() {
+            let src = INTERNAL_NODE_HEADER_SIZE + (i - 1) as usize * INTERNAL_NODE_CELL_SIZE;
+            let dst = INTERNAL_NODE_HEADER_SIZE + i as usize * INTERNAL_NODE_CELL_SIZE;
+            copy_within_a_slice(parent, src, dst, INTERNAL_NODE_CELL_SIZE);
+        }
+        set_internal_node_child(parent, index, child_page_num);
+        set_internal_node_key(parent, index, child_max_key);
+    }
+}
+
+// Write `data` into a freshly allocated chain of overflow pages and return the
+// page number of its head. Pages are filled front-to-back but linked by writing
+// them tail-first so each page's "next" pointer is known when it is written.
+fn write_overflow_chain(pager: &mut Pager, data: &[u8]) -> u32 {
+    let chunks: Vec<&[u8]> = data.chunks(OVERFLOW_PAGE_DATA_SIZE).collect();
+    let page_nums: Vec<u32> = chunks
+        .iter()
+        .map(|_| pager.allocate_overflow_page())
+        .collect();
+
+    let mut next = 0u32;
+    for (i, chunk) in chunks.iter().enumerate().rev() {
+        let page = pager.get_page(page_nums[i] as usize).unwrap();
+        page[..OVERFLOW_PAGE_HEADER_SIZE].copy_from_slice(&next.to_le_bytes());
+        page[OVERFLOW_PAGE_HEADER_SIZE..OVERFLOW_PAGE_HEADER_SIZE + chunk.len()]
+            .copy_from_slice(chunk);
+        next = page_nums[i];
+    }
+
+    page_nums[0]
+}
+
+// Turn a row into the fixed-size value slot stored in a leaf cell: a varint
+// payload length, then as much of the payload as fits locally, with any
+// remainder spilled into an overflow chain whose head is recorded in the last
+// word of the slot.
+fn build_leaf_value(
+    pager: &mut Pager, codec: &dyn RowCodec, row: &Row
+) -> [u8; LEAF_NODE_VALUE_SIZE] {
+    let mut payload = vec![0u8; ID_SIZE + 16 + row.username.len() + row.email.len()];
+    let payload_len = codec.encode(row, &mut payload);
+    payload.truncate(payload_len);
+
+    let mut full = Vec::new();
+    write_varint(&mut full, payload.len() as u64);
+    full.extend_from_slice(&payload);
+
+    let mut slot = [0u8; LEAF_NODE_VALUE_SIZE];
+    let inline_len = cmp::min(full.len(), LEAF_NODE_LOCAL_VALUE_SIZE);
+    slot[..inline_len].copy_from_slice(&full[..inline_len]);
+
+    if full.len() > LEAF_NODE_LOCAL_VALUE_SIZE {
+        let head = write_overflow_chain(pager, &full[LEAF_NODE_LOCAL_VALUE_SIZE..]);
+        slot[LEAF_NODE_LOCAL_VALUE_SIZE..].copy_from_slice(&head.to_le_bytes());
+    }
+
+    slot
+}
+
+// Reassemble the payload stored at `cell_num` of the leaf at `page_num`,
+// following the overflow chain when the value spilled past the local slot.
+fn read_leaf_value(
+    pager: &mut Pager, codec: &dyn RowCodec, page_num: usize, cell_num: u32
+) -> Row {
+    let mut full;
+    let mut next;
+    {
+        let node = pager.get_page(page_num).unwrap();
+        let value = leaf_node_value(node, cell_num);
+        full = value[..LEAF_NODE_LOCAL_VALUE_SIZE].to_vec();
+        next = u32::from_le_bytes(
+            value[LEAF_NODE_LOCAL_VALUE_SIZE..].try_into().unwrap()
+        );
+    }
+
+    while next != 0 {
+        let page = pager.get_page(next as usize).unwrap();
+        next = u32::from_le_bytes(page[..OVERFLOW_PAGE_HEADER_SIZE].try_into().unwrap());
+        full.extend_from_slice(&page[OVERFLOW_PAGE_HEADER_SIZE..]);
+    }
+
+    let (payload_len, consumed) = parse_varint(&full);
+    codec.decode(&full[consumed..consumed + payload_len as usize])
+}
+
+// Binary-search a leaf, returning a cursor positioned at the first cell whose
+// key is `>= key` (or at the end of the node if every key is smaller).
+fn leaf_node_find(table: &mut Table, page_num: usize, key: u32) -> Cursor<'_> {
+    let (cell_num, num_cells) = {
+        let node = table.pager.get_page(page_num).unwrap();
+        let num_cells = leaf_node_num_cells(node);
+
+        let mut low = 0;
+        let mut high = num_cells;
+        while low < high {
+            let mid = (low + high) / 2;
+            if key <= leaf_node_key(node, mid) {
+                high = mid;
+            } else {
+                low = mid + 1;
+            }
+        }
+        (low, num_cells)
+    };
+
+    Cursor {
+        table,
+        page_num,
+        cell_num: cell_num as usize,
+        end_of_table: cell_num == num_cells,
+    }
+}
+
+// Binary-search an internal node's keys, returning the index of the child that
+// should contain `key` (an index of `num_keys` means the right child).
+fn internal_node_find_child(node: &mut [u8], key: u32) -> u32 {
+    let num_keys = internal_node_num_keys(node);
+
+    let mut low = 0;
+    let mut high = num_keys;
+    while low < high {
+        let mid = (low + high) / 2;
+        if internal_node_key(node, mid) >= key {
+            high = mid;
+        } else {
+            low = mid + 1;
+        }
+    }
+
+    low
+}
+
+// Resolve the child index from `internal_node_find_child` to its page number.
+fn internal_node_child_for_key(node: &mut [u8], key: u32) -> u32 {
+    let child_index = internal_node_find_child(node, key);
+    if child_index < internal_node_num_keys(node) {
+        internal_node_child(node, child_index)
+    } else {
+        internal_node_right_child(node)
+    }
+}
+
+// Recurse from the given internal node into the child that should hold `key`,
+// descending through any further internal nodes until a leaf is reached.
+fn internal_node_find(table: &mut Table, page_num: usize, key: u32) -> Cursor<'_> {
+    let child_page_num = {
+        let node = table.pager.get_page(page_num).unwrap();
+        internal_node_child_for_key(node, key)
+    };
+    table_find(table, child_page_num as usize, key)
+}
+
+// Descend from `page_num` to the leaf that should hold `key` and return a
+// cursor positioned within it.
+fn table_find(table: &mut Table, page_num: usize, key: u32) -> Cursor<'_> {
+    let node_type = {
+        let node = table.pager.get_page(page_num).unwrap();
+        get_node_type(node)
+    };
+    match node_type {
+        NodeType::Leaf => leaf_node_find(table, page_num, key),
+        NodeType::Internal => internal_node_find(table, page_num, key),
+    }
+}
 
 fn leaf_node_insert(cursor: &mut Cursor, key: u32, value: &Row) {
-    let node = cursor.table.pager.get_page(cursor.page_num).unwrap();
+    let slot = build_leaf_value(
+        &mut cursor.table.pager, cursor.table.codec.as_ref(), value
+    );
+
+    let num_cells = {
+        let node = cursor.table.pager.get_page(cursor.page_num).unwrap();
+        leaf_node_num_cells(node)
+    };
 
-    let num_cells = leaf_node_num_cells(node);
     if num_cells as usize >= LEAF_NODE_MAX_CELLS {
-        panic!("Need to implement splitting a leaf node.")
+        leaf_node_split_and_insert(cursor, key, &slot);
+        return;
     }
 
+    let node = cursor.table.pager.get_page(cursor.page_num).unwrap();
+
     if cursor.cell_num < num_cells as usize {
-        for i in num_cells..cursor.cell_num as u32 {
+        for i in (cursor.cell_num as u32 + 1..=num_cells).rev() {
             shift_cell_right(node, i);
         }
     }
 
     leaf_node_set_num_cells(node, num_cells + 1);
     leaf_node_set_key(node, cursor.cell_num as u32, key);
-    value.serialize(leaf_node_value(node, cursor.cell_num as u32));
+    leaf_node_value(node, cursor.cell_num as u32).copy_from_slice(&slot);
 }
 
 struct Cursor<'a> {
@@ -362,30 +901,15 @@ struct Cursor<'a> {
 
 impl <'a> Cursor<'a> {
     fn table_start(table: &'a mut Table) -> Self {
-        let page_num = table.root_page_num;
-        let root_node = table.pager.get_page(page_num as usize).unwrap();
-        let num_cells = leaf_node_num_cells(root_node);
-        let end_of_table = num_cells == 0;
-
-        Cursor {
-            table,
-            page_num: page_num as usize,
-            cell_num: 0,
-            end_of_table,
-        }
-    }
+        let key = 0;
+        let page_num = table.root_page_num as usize;
+        let mut cursor = table_find(table, page_num, key);
 
-    fn table_end(table: &'a mut Table) -> Self {
-        let page_num = table.root_page_num;
-        let root_node = table.pager.get_page(table.root_page_num as usize).unwrap();
-        let cell_num = leaf_node_num_cells(root_node);
+        let node = cursor.table.pager.get_page(cursor.page_num).unwrap();
+        let num_cells = leaf_node_num_cells(node);
+        cursor.end_of_table = num_cells == 0;
 
-        Cursor {
-            table,
-            page_num: page_num as usize,
-            cell_num: cell_num as usize,
-            end_of_table: true,
-        }
+        cursor
     }
 
     fn advance(&mut self) {
@@ -394,33 +918,52 @@ impl <'a> Cursor<'a> {
 
         self.cell_num += 1;
         if self.cell_num >= leaf_node_num_cells(node) as usize {
-            self.end_of_table = true;
+            let next_page_num = leaf_node_next_leaf(node);
+            if next_page_num == 0 {
+                self.end_of_table = true;
+            } else {
+                self.page_num = next_page_num as usize;
+                self.cell_num = 0;
+            }
         }
     }
 
-    fn value(&mut self) -> &mut [u8] {
-        let page_num = self.page_num;
-        let page = self.table.pager.get_page(page_num).unwrap();
-
-        leaf_node_value(page, self.cell_num as u32)
+    fn row(&mut self) -> Row {
+        read_leaf_value(
+            &mut self.table.pager,
+            self.table.codec.as_ref(),
+            self.page_num,
+            self.cell_num as u32,
+        )
     }
 }
 
 #[derive(Debug)]
 enum ExecuteError {
     TableFull,
+    DuplicateKey,
 }
 
 fn execute_insert(statement: &Statement, table: &mut Table) -> Result<(), ExecuteError> {
-    let node = table.pager.get_page(table.root_page_num as usize).unwrap();
-
-    if leaf_node_num_cells(node) >= LEAF_NODE_MAX_CELLS as u32 {
+    if table.pager.num_pages as usize >= TABLE_MAX_PAGES {
         return Err(ExecuteError::TableFull);
     }
 
     let row_to_insert = statement.row_to_insert.as_ref().unwrap();
-    let mut cursor = Cursor::table_end(table);
-    leaf_node_insert(&mut cursor, row_to_insert.id, &row_to_insert);
+    let key = row_to_insert.id;
+    let page_num = table.root_page_num as usize;
+    let mut cursor = table_find(table, page_num, key);
+
+    {
+        let node = cursor.table.pager.get_page(cursor.page_num).unwrap();
+        let num_cells = leaf_node_num_cells(node);
+        if (cursor.cell_num as u32) < num_cells
+            && leaf_node_key(node, cursor.cell_num as u32) == key {
+            return Err(ExecuteError::DuplicateKey);
+        }
+    }
+
+    leaf_node_insert(&mut cursor, key, row_to_insert);
 
     Ok(())
 }
@@ -430,7 +973,7 @@ fn execute_select(statement: &Statement, table: &mut Table) -> Result<(), Execut
     let mut cursor = Cursor::table_start(table);
 
     while !cursor.end_of_table {
-        let row = Row::deserialize(cursor.value());
+        let row = cursor.row();
         row.print();
         cursor.advance();
     }
@@ -441,7 +984,7 @@ fn execute_select(statement: &Statement, table: &mut Table) -> Result<(), Execut
 fn execute_statement(
     statement: &Statement, table: &mut Table
 ) -> Result<(), ExecuteError> {
-    return match statement.statement_type {
+    match statement.statement_type {
         StatementType::Insert => execute_insert(statement, table),
         StatementType::Select => execute_select(statement, table),
     }
@@ -457,26 +1000,103 @@ fn read_input(prompt: &str) -> io::Result<String> {
     Ok(input_buffer.trim().to_string())
 }
 
+fn indent(level: u32) {
+    for _ in 0..level {
+        print!("  ");
+    }
+}
+
+// Recursively pretty-print the tree rooted at `page_num`, indenting one level
+// per depth: leaves list their cell count and keys, internal nodes print their
+// size and then recurse into every child pointer and the right child.
+fn print_tree(pager: &mut Pager, page_num: u32, indentation_level: u32) {
+    let node_type = {
+        let node = pager.get_page(page_num as usize).unwrap();
+        get_node_type(node)
+    };
+
+    match node_type {
+        NodeType::Leaf => {
+            let num_cells = {
+                let node = pager.get_page(page_num as usize).unwrap();
+                leaf_node_num_cells(node)
+            };
+            indent(indentation_level);
+            println!("- leaf (size {})", num_cells);
+            for i in 0..num_cells {
+                let key = {
+                    let node = pager.get_page(page_num as usize).unwrap();
+                    leaf_node_key(node, i)
+                };
+                indent(indentation_level + 1);
+                println!("- {}", key);
+            }
+        },
+        NodeType::Internal => {
+            let num_keys = {
+                let node = pager.get_page(page_num as usize).unwrap();
+                internal_node_num_keys(node)
+            };
+            indent(indentation_level);
+            println!("- internal (size {})", num_keys);
+            for i in 0..num_keys {
+                let child = {
+                    let node = pager.get_page(page_num as usize).unwrap();
+                    internal_node_child(node, i)
+                };
+                print_tree(pager, child, indentation_level + 1);
+
+                let key = {
+                    let node = pager.get_page(page_num as usize).unwrap();
+                    internal_node_key(node, i)
+                };
+                indent(indentation_level + 1);
+                println!("- key {}", key);
+            }
+
+            let right_child = {
+                let node = pager.get_page(page_num as usize).unwrap();
+                internal_node_right_child(node)
+            };
+            print_tree(pager, right_child, indentation_level + 1);
+        },
+    }
+}
+
 fn do_meta_command(command: &str, table: &mut Table) -> Result<(), ()> {
     match command {
         ".exit" => {
             table.close();
             process::exit(0);
         },
-        _ => return Err(())
-    };
+        ".constants" => {
+            println!("Constants:");
+            println!("ROW_SIZE: {}", ROW_SIZE);
+            println!("COMMON_NODE_HEADER_SIZE: {}", COMMON_NODE_HEADER_SIZE);
+            println!("LEAF_NODE_HEADER_SIZE: {}", LEAF_NODE_HEADER_SIZE);
+            println!("LEAF_NODE_CELL_SIZE: {}", LEAF_NODE_CELL_SIZE);
+            println!("LEAF_NODE_SPACE_FOR_CELLS: {}", LEAF_NODE_SPACE_FOR_CELLS);
+            println!("LEAF_NODE_MAX_CELLS: {}", LEAF_NODE_MAX_CELLS);
+            Ok(())
+        },
+        ".btree" => {
+            println!("Tree:");
+            print_tree(&mut table.pager, table.root_page_num, 0);
+            Ok(())
+        },
+        _ => Err(())
+    }
 }
 
 fn main() -> io::Result<()> {
     let args: Vec<String> = env::args().collect();
-    let filename;
-    if args.len() > 1 {
-        filename = args[1].as_str();
+    let filename = if args.len() > 1 {
+        args[1].as_str()
     } else {
-        filename = "db.dat";
-    }
+        "db.dat"
+    };
 
-    let mut table = Table::new(&filename);
+    let mut table = Table::new(filename);
 
     loop {
         let input = read_input("db > ")?;
@@ -500,6 +1120,10 @@ fn main() -> io::Result<()> {
                             ExecuteError::TableFull => {
                                 println!("Error: Table full.");
                                 continue;
+                            },
+                            ExecuteError::DuplicateKey => {
+                                println!("Error: Duplicate key.");
+                                continue;
                             }
                         }
                     }
@@ -514,10 +1138,6 @@ fn main() -> io::Result<()> {
                     println!("Syntax error. Count not parse statement.");
                     continue;
                 },
-                PrepareError::StringTooLong => {
-                    println!("String is too long.");
-                    continue;
-                },
                 PrepareError::NegativeId => {
                     println!("ID must be positive.");
                     continue;
@@ -527,3 +1147,113 @@ fn main() -> io::Result<()> {
     }
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+    fn temp_db_path() -> String {
+        let n = COUNTER.fetch_add(1, Ordering::SeqCst);
+        let mut path = std::env::temp_dir();
+        path.push(format!("db_test_{}_{}.dat", std::process::id(), n));
+        path.to_str().unwrap().to_string()
+    }
+
+    fn insert(table: &mut Table, id: u32, username: &str, email: &str)
+        -> Result<(), ExecuteError>
+    {
+        let statement = Statement {
+            statement_type: StatementType::Insert,
+            row_to_insert: Some(Row {
+                id,
+                username: username.to_string(),
+                email: email.to_string(),
+            }),
+        };
+        execute_insert(&statement, table)
+    }
+
+    fn collect_ids(table: &mut Table) -> Vec<u32> {
+        let mut cursor = Cursor::table_start(table);
+        let mut ids = Vec::new();
+        while !cursor.end_of_table {
+            ids.push(cursor.row().id);
+            cursor.advance();
+        }
+        ids
+    }
+
+    fn select_all(table: &mut Table) -> Vec<Row> {
+        let mut cursor = Cursor::table_start(table);
+        let mut rows = Vec::new();
+        while !cursor.end_of_table {
+            rows.push(cursor.row());
+            cursor.advance();
+        }
+        rows
+    }
+
+    #[test]
+    fn multi_level_splits_preserve_sorted_order() {
+        let path = temp_db_path();
+        {
+            let mut table = Table::new(&path);
+            // Insert in descending order: enough rows to split the root leaf and
+            // then repeatedly split non-root leaves, exercising the parent update.
+            let n = 60u32;
+            for id in (1..=n).rev() {
+                insert(&mut table, id, "user", "user@example.com").unwrap();
+            }
+            let expected: Vec<u32> = (1..=n).collect();
+            assert_eq!(collect_ids(&mut table), expected);
+            table.close();
+        }
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn overflow_value_round_trips_through_disk() {
+        let path = temp_db_path();
+        // An email several pages long cannot fit the local slot and must spill
+        // into an overflow chain.
+        let big_email = "x".repeat(3 * PAGE_SIZE + 7);
+        {
+            let mut table = Table::new(&path);
+            insert(&mut table, 1, "over", &big_email).unwrap();
+            table.close();
+        }
+        {
+            let mut table = Table::new(&path);
+            let rows = select_all(&mut table);
+            assert_eq!(rows.len(), 1);
+            assert_eq!(rows[0].id, 1);
+            assert_eq!(rows[0].username, "over");
+            assert_eq!(rows[0].email, big_email);
+            table.close();
+        }
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn manual_codec_round_trips_through_disk() {
+        let path = temp_db_path();
+        {
+            let mut table = Table::with_codec(&path, Box::new(ManualCodec));
+            insert(&mut table, 7, "manual", "manual@example.com").unwrap();
+            table.close();
+        }
+        {
+            let mut table = Table::with_codec(&path, Box::new(ManualCodec));
+            let rows = select_all(&mut table);
+            assert_eq!(rows.len(), 1);
+            assert_eq!(rows[0].id, 7);
+            assert_eq!(rows[0].username, "manual");
+            assert_eq!(rows[0].email, "manual@example.com");
+            table.close();
+        }
+        std::fs::remove_file(&path).ok();
+    }
+}